@@ -0,0 +1,16 @@
+use crate::Row;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Backend de stockage vers lequel les lignes extraites des fichiers Huawei sont insérées.
+///
+/// Le pipeline d'extraction (`extract_rows`) reste agnostique du backend : il produit des
+/// `Vec<Row>` que n'importe quelle implémentation de ce trait peut consommer.
+#[async_trait]
+pub(crate) trait Importer: Send + Sync {
+    /// Prépare le backend avant la première insertion (création de table, etc.)
+    async fn ensure_schema(&self) -> Result<()>;
+
+    /// Insère un lot de lignes et retourne le nombre de lignes effectivement insérées.
+    async fn insert_rows(&self, rows: &[Row]) -> Result<u64>;
+}
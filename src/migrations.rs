@@ -0,0 +1,136 @@
+use anyhow::{Context, Result};
+use deadpool_postgres::GenericClient;
+
+/// Une migration de schéma, identifiée par un numéro de version croissant et jamais rejouée
+/// une fois enregistrée dans `schema_migrations`.
+struct Migration {
+    version: i32,
+    description: &'static str,
+    sql: String,
+}
+
+/// Crée (si besoin) la table `table_name` avec la contrainte d'unicité nécessaire à
+/// `ON CONFLICT DO NOTHING`, la convertit en hypertable TimescaleDB, et enregistre les
+/// versions appliquées dans `schema_migrations` pour ne jamais les rejouer.
+///
+/// Les versions appliquées sont scopées par `(version, table_name)` : `--table` permet de
+/// cibler une table différente à chaque exécution contre la même base, et chacune doit
+/// recevoir ses propres migrations plutôt que d'être court-circuitée par celles d'une autre.
+pub(crate) async fn run(client: &impl GenericClient, table_name: &str) -> Result<()> {
+    validate_identifier(table_name)?;
+
+    client
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations ( \
+                 version INTEGER NOT NULL, \
+                 table_name TEXT NOT NULL, \
+                 applied_at TIMESTAMPTZ NOT NULL DEFAULT now(), \
+                 PRIMARY KEY (version, table_name) \
+             )",
+        )
+        .await
+        .context("Impossible de créer la table schema_migrations")?;
+
+    let migrations = vec![
+        Migration {
+            version: 1,
+            description: "créer la table cible",
+            sql: format!(
+                "CREATE TABLE IF NOT EXISTS {table} ( \
+                     bucket TIMESTAMPTZ NOT NULL, \
+                     source TEXT NOT NULL, \
+                     measurement TEXT NOT NULL, \
+                     value FLOAT8 NOT NULL, \
+                     UNIQUE (bucket, source, measurement) \
+                 )",
+                table = table_name
+            ),
+        },
+        Migration {
+            version: 2,
+            description: "convertir en hypertable TimescaleDB",
+            sql: format!(
+                "SELECT create_hypertable('{table}', 'bucket', if_not_exists => TRUE)",
+                table = table_name
+            ),
+        },
+    ];
+
+    for migration in migrations {
+        let already_applied: bool = client
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM schema_migrations WHERE version = $1 AND table_name = $2)",
+                &[&migration.version, &table_name],
+            )
+            .await
+            .context("Impossible de vérifier les migrations déjà appliquées")?
+            .get(0);
+
+        if already_applied {
+            continue;
+        }
+
+        client
+            .batch_execute(&migration.sql)
+            .await
+            .with_context(|| {
+                format!(
+                    "Échec de la migration {} ({}) pour la table {}",
+                    migration.version, migration.description, table_name
+                )
+            })?;
+
+        client
+            .execute(
+                "INSERT INTO schema_migrations (version, table_name) VALUES ($1, $2)",
+                &[&migration.version, &table_name],
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Impossible d'enregistrer la migration {} pour la table {}",
+                    migration.version, table_name
+                )
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Garde-fou contre l'injection SQL via `--table` : seuls des identifiants (éventuellement
+/// qualifiés par un schéma, ex: `public.energy_measurements`) sont acceptés.
+pub(crate) fn validate_identifier(name: &str) -> Result<()> {
+    let is_valid = !name.is_empty()
+        && name
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+
+    if is_valid {
+        Ok(())
+    } else {
+        anyhow::bail!("Nom de table invalide : {:?}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_identifier;
+
+    #[test]
+    fn accepts_schema_qualified_identifier() {
+        assert!(validate_identifier("public.energy_measurements").is_ok());
+    }
+
+    #[test]
+    fn rejects_sql_injection_attempt() {
+        assert!(validate_identifier("foo; DROP TABLE x--").is_err());
+    }
+
+    #[test]
+    fn rejects_empty_or_dotted_empty_segments() {
+        assert!(validate_identifier("").is_err());
+        assert!(validate_identifier(".energy_measurements").is_err());
+        assert!(validate_identifier("public.").is_err());
+        assert!(validate_identifier("public..energy_measurements").is_err());
+    }
+}
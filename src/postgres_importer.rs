@@ -0,0 +1,211 @@
+use crate::importer::Importer;
+use crate::migrations;
+use crate::Row;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
+use futures::pin_mut;
+use tokio_postgres::binary_copy::BinaryCopyInWriter;
+use tokio_postgres::types::Type;
+use tokio_postgres::{NoTls, Transaction};
+
+/// Backend PostgreSQL/TimescaleDB, basé sur un pool de connexions partagé entre fichiers
+pub(crate) struct PostgresImporter {
+    pool: Pool,
+    table: String,
+    migrate: bool,
+}
+
+impl PostgresImporter {
+    /// Construit un pool de connexions dimensionné par `max_connections`. `table` désigne
+    /// la table cible (créée par les migrations sauf si `migrate` est `false`).
+    pub(crate) fn new(
+        db_url: &str,
+        max_connections: usize,
+        table: String,
+        migrate: bool,
+    ) -> Result<Self> {
+        migrations::validate_identifier(&table)?;
+
+        let pg_config: tokio_postgres::Config =
+            db_url.parse().context("URL PostgreSQL invalide")?;
+
+        let manager_config = ManagerConfig {
+            recycling_method: RecyclingMethod::Fast,
+        };
+        let manager = Manager::from_config(pg_config, NoTls, manager_config);
+
+        let pool = Pool::builder(manager)
+            .max_size(max_connections)
+            .build()
+            .context("Impossible de construire le pool de connexions")?;
+
+        Ok(Self {
+            pool,
+            table,
+            migrate,
+        })
+    }
+}
+
+#[async_trait]
+impl Importer for PostgresImporter {
+    async fn ensure_schema(&self) -> Result<()> {
+        if !self.migrate {
+            return Ok(());
+        }
+
+        let client = self
+            .pool
+            .get()
+            .await
+            .context("Impossible d'obtenir une connexion du pool")?;
+
+        migrations::run(&client, &self.table).await
+    }
+
+    async fn insert_rows(&self, rows: &[Row]) -> Result<u64> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .context("Impossible d'obtenir une connexion du pool")?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .context("Impossible de démarrer une transaction")?;
+
+        // Un savepoint isole la tentative COPY : si elle échoue, Postgres passe la
+        // transaction en état "aborted" et refuserait le repli ligne par ligne sans ce
+        // rollback partiel (la transaction entière resterait inutilisable).
+        transaction
+            .batch_execute("SAVEPOINT copy_attempt")
+            .await
+            .context("Impossible de créer le savepoint")?;
+
+        let inserted = match copy_insert_rows(&transaction, &self.table, rows).await {
+            Ok(inserted) => inserted,
+            Err(e) => {
+                eprintln!(
+                    "COPY binaire indisponible ({}), repli sur l'insertion ligne par ligne",
+                    e
+                );
+                transaction
+                    .batch_execute("ROLLBACK TO SAVEPOINT copy_attempt")
+                    .await
+                    .context("Impossible de revenir au savepoint")?;
+                row_by_row_insert_rows(&transaction, &self.table, rows).await?
+            }
+        };
+
+        transaction
+            .commit()
+            .await
+            .context("Impossible de valider la transaction")?;
+
+        Ok(inserted)
+    }
+}
+
+/// Insère les lignes via `COPY ... FROM STDIN (FORMAT binary)` dans une table temporaire,
+/// puis les reporte vers la table cible avec `ON CONFLICT DO NOTHING` pour préserver
+/// l'idempotence (COPY seul ne supporte pas ON CONFLICT).
+async fn copy_insert_rows(transaction: &Transaction<'_>, table: &str, rows: &[Row]) -> Result<u64> {
+    transaction
+        .batch_execute(
+            "CREATE TEMP TABLE staging_rows ( \
+                 bucket TIMESTAMPTZ, \
+                 source TEXT, \
+                 measurement TEXT, \
+                 value FLOAT8 \
+             ) ON COMMIT DROP",
+        )
+        .await
+        .context("Impossible de créer la table temporaire")?;
+
+    let sink = transaction
+        .copy_in("COPY staging_rows (bucket, source, measurement, value) FROM STDIN (FORMAT binary)")
+        .await
+        .context("Impossible de démarrer le COPY")?;
+
+    let writer = BinaryCopyInWriter::new(
+        sink,
+        &[Type::TIMESTAMPTZ, Type::TEXT, Type::TEXT, Type::FLOAT8],
+    );
+    pin_mut!(writer);
+
+    for row in rows {
+        let naive_dt = row
+            .bucket
+            .and_hms_opt(0, 0, 0)
+            .context("Impossible de créer le timestamp")?;
+        let timestamp = Utc.from_utc_datetime(&naive_dt);
+
+        writer
+            .as_mut()
+            .write(&[&timestamp, &row.source, &row.measurement, &row.value])
+            .await
+            .with_context(|| {
+                format!(
+                    "Erreur lors du COPY de {} {} {} {}",
+                    row.bucket, row.source, row.measurement, row.value
+                )
+            })?;
+    }
+
+    writer.finish().await.context("Impossible de terminer le COPY")?;
+
+    let inserted = transaction
+        .execute(
+            &format!(
+                "INSERT INTO {table} (bucket, source, measurement, value) \
+                 SELECT bucket, source, measurement, value FROM staging_rows \
+                 ON CONFLICT DO NOTHING",
+            ),
+            &[],
+        )
+        .await
+        .context("Impossible d'insérer depuis la table temporaire")?;
+
+    Ok(inserted)
+}
+
+/// Repli historique : insère les lignes une par une via une requête préparée.
+/// Utilisé uniquement si le chemin COPY binaire échoue (ex: serveur trop ancien).
+async fn row_by_row_insert_rows(transaction: &Transaction<'_>, table: &str, rows: &[Row]) -> Result<u64> {
+    let statement = transaction
+        .prepare(&format!(
+            "INSERT INTO {table} (bucket, source, measurement, value) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT DO NOTHING",
+        ))
+        .await
+        .context("Impossible de préparer la requête INSERT")?;
+
+    let mut inserted = 0u64;
+    for row in rows {
+        let naive_dt = row
+            .bucket
+            .and_hms_opt(0, 0, 0)
+            .context("Impossible de créer le timestamp")?;
+        let timestamp = Utc.from_utc_datetime(&naive_dt);
+
+        let affected = transaction
+            .execute(
+                &statement,
+                &[&timestamp, &row.source, &row.measurement, &row.value],
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Erreur lors de l'insertion de {} {} {} {}",
+                    row.bucket, row.source, row.measurement, row.value
+                )
+            })?;
+        inserted += affected;
+    }
+
+    Ok(inserted)
+}
@@ -0,0 +1,111 @@
+use crate::importer::Importer;
+use crate::{build_importer, rows_from_data, Args, HuaweiFile};
+use anyhow::{Context, Result};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// Corps de `POST /import` : un fichier Huawei accompagné de l'année/mois qu'il couvre
+/// (habituellement déduits du nom de fichier en mode batch).
+#[derive(Debug, Deserialize)]
+struct ImportRequest {
+    year: i32,
+    month: u32,
+    #[serde(flatten)]
+    file: HuaweiFile,
+}
+
+#[derive(Debug, Serialize)]
+struct ImportResponse {
+    rows_extracted: usize,
+    rows_inserted: u64,
+    errors: Vec<String>,
+}
+
+struct ServeState {
+    importer: Arc<dyn Importer>,
+}
+
+/// Démarre le mode `serve` : un service HTTP de longue durée qui reçoit les fichiers Huawei
+/// par POST au lieu de les lire sur disque, pour être branché sur un pipeline de collecte
+/// automatisé. Réutilise le même backend de stockage et le même pipeline d'extraction que
+/// le mode batch.
+pub(crate) async fn run(args: Args, port: u16) -> Result<()> {
+    let db_url = args
+        .db_url
+        .as_deref()
+        .context("--db-url est requis en mode serve")?;
+
+    let importer = build_importer(db_url, args.max_connections, args.table.clone(), !args.no_migrate)
+        .await
+        .context("Impossible d'initialiser le backend de stockage")?;
+
+    importer
+        .ensure_schema()
+        .await
+        .context("Impossible de préparer le schéma")?;
+
+    let state = Arc::new(ServeState { importer });
+
+    let app = Router::new()
+        .route("/import", post(handle_import))
+        .with_state(state);
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    eprintln!("huawei-importer : écoute sur http://{}", addr);
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Impossible d'écouter sur {}", addr))?;
+
+    axum::serve(listener, app)
+        .await
+        .context("Erreur du serveur HTTP")?;
+
+    Ok(())
+}
+
+async fn handle_import(
+    State(state): State<Arc<ServeState>>,
+    Json(payload): Json<ImportRequest>,
+) -> (StatusCode, Json<ImportResponse>) {
+    let rows = rows_from_data(&payload.file.data, payload.year, payload.month);
+    let rows_extracted = rows.len();
+
+    // Une insertion par mesure : un échec (ex: contrainte violée sur `active_energy_exported`)
+    // ne doit pas faire échouer les mesures qui, elles, s'insèrent sans problème.
+    let mut rows_by_measurement: BTreeMap<&'static str, Vec<crate::Row>> = BTreeMap::new();
+    for row in rows {
+        rows_by_measurement.entry(row.measurement).or_default().push(row);
+    }
+
+    let mut rows_inserted = 0u64;
+    let mut errors = Vec::new();
+
+    for (measurement, rows) in rows_by_measurement {
+        match state.importer.insert_rows(&rows).await {
+            Ok(inserted) => rows_inserted += inserted,
+            Err(e) => errors.push(format!("{}: {}", measurement, e)),
+        }
+    }
+
+    let status = if errors.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+
+    (
+        status,
+        Json(ImportResponse {
+            rows_extracted,
+            rows_inserted,
+            errors,
+        }),
+    )
+}
@@ -0,0 +1,83 @@
+use crate::importer::Importer;
+use crate::migrations::validate_identifier;
+use crate::Row;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use rusqlite::params;
+use tokio_rusqlite::Connection;
+
+/// Backend SQLite : matérialise les lignes extraites dans un fichier local, pour les
+/// utilisateurs sans instance TimescaleDB (inspection ou rejeu ultérieur).
+pub(crate) struct SqliteImporter {
+    conn: Connection,
+    table: String,
+}
+
+impl SqliteImporter {
+    pub(crate) async fn new(path: &str, table: String) -> Result<Self> {
+        validate_identifier(&table)?;
+
+        let conn = Connection::open(path)
+            .await
+            .with_context(|| format!("Impossible d'ouvrir la base SQLite {:?}", path))?;
+        Ok(Self { conn, table })
+    }
+}
+
+#[async_trait]
+impl Importer for SqliteImporter {
+    async fn ensure_schema(&self) -> Result<()> {
+        let table = self.table.clone();
+        self.conn
+            .call(move |conn| {
+                conn.execute_batch(&format!(
+                    "CREATE TABLE IF NOT EXISTS {table} ( \
+                         bucket TEXT NOT NULL, \
+                         source TEXT NOT NULL, \
+                         measurement TEXT NOT NULL, \
+                         value REAL NOT NULL, \
+                         UNIQUE(bucket, source, measurement) \
+                     )",
+                ))?;
+                Ok(())
+            })
+            .await
+            .context("Impossible de créer la table SQLite")
+    }
+
+    async fn insert_rows(&self, rows: &[Row]) -> Result<u64> {
+        // `tokio_rusqlite` exécute la closure sur un thread dédié : on convertit les lignes
+        // en valeurs possédées pour franchir la frontière `move`.
+        let owned: Vec<(String, String, String, f64)> = rows
+            .iter()
+            .map(|row| {
+                (
+                    row.bucket.to_string(),
+                    row.source.to_string(),
+                    row.measurement.to_string(),
+                    row.value,
+                )
+            })
+            .collect();
+        let table = self.table.clone();
+
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let mut inserted = 0u64;
+                {
+                    let mut stmt = tx.prepare(&format!(
+                        "INSERT OR IGNORE INTO {table} (bucket, source, measurement, value) \
+                         VALUES (?1, ?2, ?3, ?4)",
+                    ))?;
+                    for (bucket, source, measurement, value) in &owned {
+                        inserted += stmt.execute(params![bucket, source, measurement, value])? as u64;
+                    }
+                }
+                tx.commit()?;
+                Ok(inserted)
+            })
+            .await
+            .context("Impossible d'insérer les lignes dans SQLite")
+    }
+}